@@ -12,6 +12,7 @@ use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
+use dialoguer::{Confirm, Input};
 use libp2p::multiaddr::{Multiaddr, Protocol};
 use libp2p::multihash::Multihash;
 use libp2p::PeerId;
@@ -25,7 +26,8 @@ use thiserror::Error;
 
 use crate::cli;
 
-/// Base directory contains global config and chain directories.
+/// Base directory name, nested under the OS-standard user data directory by
+/// [`default_base_dir`] unless an explicit `--base-dir` override is given.
 pub const DEFAULT_BASE_DIR: &str = ".anoma";
 /// Default WASM dir. Note that WASM dirs are nested in chain dirs.
 pub const DEFAULT_WASM_DIR: &str = "wasm";
@@ -38,14 +40,31 @@ pub const FILENAME: &str = "config.toml";
 pub const TENDERMINT_DIR: &str = "tendermint";
 /// Chain-specific Anoma DB. Nested in chain dirs.
 pub const DB_DIR: &str = "db";
+/// The persisted libp2p node identity keypair. Nested in chain dirs,
+/// alongside the DB and Tendermint dirs.
+pub const GOSSIPER_KEY_FILE: &str = "gossiper_key";
+
+/// Resolve the default base directory used when no explicit `--base-dir` is
+/// given: [`DEFAULT_BASE_DIR`] nested under the OS-standard user data
+/// location (the XDG data dir on Linux, `~/Library/Application Support` on
+/// macOS, `%APPDATA%` on Windows), so that running the node from different
+/// working directories no longer silently targets different data dirs.
+/// Falls back to the bare relative [`DEFAULT_BASE_DIR`] if the platform data
+/// dir can't be determined.
+pub fn default_base_dir() -> PathBuf {
+    directories::BaseDirs::new()
+        .map(|dirs| dirs.data_dir().join(DEFAULT_BASE_DIR))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_BASE_DIR))
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Config {
     pub wasm_dir: PathBuf,
     pub ledger: Ledger,
     pub intent_gossiper: IntentGossiper,
-    // TODO allow to configure multiple matchmakers
-    pub matchmaker: Matchmaker,
+    /// Each matchmaker runs its own intent-matching strategy against the
+    /// gossip network, subscribed to its own topic.
+    pub matchmakers: Vec<Matchmaker>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -124,6 +143,225 @@ pub struct Tendermint {
     pub instrumentation_prometheus: bool,
     pub instrumentation_prometheus_listen_addr: SocketAddr,
     pub instrumentation_namespace: String,
+    /// When set and `tendermint_mode` is [`TendermintMode::Validator`], the
+    /// consensus key is never read from this node's disk. Instead, every
+    /// vote/proposal signing request is forwarded to the out-of-process
+    /// signer described here and the node blocks until it returns a
+    /// signature. This keeps key custody separate from the full node, the
+    /// same way dedicated signing daemons do.
+    pub priv_validator_remote: Option<RemoteSigner>,
+}
+
+/// Connection details for an out-of-process validator signer (e.g. a KMS or
+/// a privval-style signing daemon).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RemoteSigner {
+    /// Where to reach the remote signer, either over TCP or through a Unix
+    /// domain socket.
+    pub address: RemoteSignerAddress,
+    /// The remote signer's expected long-term Ed25519 identity public key.
+    /// When connecting, the signer must sign a random challenge nonce with
+    /// the matching private key, and the connection is rejected unless
+    /// that signature verifies against this key. No ephemeral key exchange
+    /// takes place and the channel is not encrypted.
+    pub identity_pubkey: tendermint::PublicKey,
+    /// How long to wait for the TCP connection to the signer before giving
+    /// up. It does not apply to a Unix socket connection or to the
+    /// handshake.
+    pub connect_timeout: Timeout,
+    /// Read and write timeout on the channel once it is established: a
+    /// sign request fails if the signer does not respond within it.
+    pub request_timeout: Timeout,
+    /// Not used by [`RemoteSignerClient`], which does not reconnect: once
+    /// the channel is dropped, every later sign request fails.
+    pub reconnect_backoff: Timeout,
+}
+
+/// Where to reach a [`RemoteSigner`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RemoteSignerAddress {
+    Tcp(SocketAddr),
+    Unix(PathBuf),
+}
+
+/// The consensus height, round and step a signature was requested for,
+/// used by [`RemoteSignerClient`] to refuse to forward a request at or
+/// below a state it has already signed for over the same channel. The last
+/// signed state is kept in memory only: it is not persisted across a
+/// restart and not shared with another instance of the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SignState {
+    pub height: u64,
+    pub round: u64,
+    pub step: u8,
+}
+
+/// An authenticated channel to a [`RemoteSigner`], used to forward
+/// vote/proposal signing requests instead of holding the consensus key on
+/// this node.
+pub struct RemoteSignerClient {
+    conn: RemoteSignerConn,
+    last_signed: Option<SignState>,
+}
+
+enum RemoteSignerConn {
+    Tcp(std::net::TcpStream),
+    Unix(std::os::unix::net::UnixStream),
+}
+
+impl std::io::Read for RemoteSignerConn {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Tcp(s) => s.read(buf),
+            Self::Unix(s) => s.read(buf),
+        }
+    }
+}
+
+impl std::io::Write for RemoteSignerConn {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Tcp(s) => s.write(buf),
+            Self::Unix(s) => s.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Tcp(s) => s.flush(),
+            Self::Unix(s) => s.flush(),
+        }
+    }
+}
+
+impl RemoteSignerClient {
+    /// Establish the channel described by `signer` and authenticate the
+    /// remote signer: it proves ownership of `signer.identity_pubkey` by
+    /// signing a locally-generated challenge nonce, which is verified
+    /// before the channel is trusted with any sign request. This node does
+    /// not authenticate itself to the signer and the channel is not
+    /// encrypted. Only the TCP connect is bounded by
+    /// `signer.connect_timeout`; sign requests are then bounded by
+    /// `signer.request_timeout`.
+    pub fn connect(signer: &RemoteSigner) -> Result<Self> {
+        use std::io::Read;
+
+        let connect_timeout = signer.connect_timeout.into();
+        let mut conn = match &signer.address {
+            RemoteSignerAddress::Tcp(addr) => RemoteSignerConn::Tcp(
+                std::net::TcpStream::connect_timeout(addr, connect_timeout)
+                    .map_err(Error::RemoteSignerConnect)?,
+            ),
+            RemoteSignerAddress::Unix(path) => RemoteSignerConn::Unix(
+                std::os::unix::net::UnixStream::connect(path)
+                    .map_err(Error::RemoteSignerConnect)?,
+            ),
+        };
+
+        let nonce: [u8; 32] = rand::random();
+        conn.write_all(&nonce).map_err(Error::RemoteSignerConnect)?;
+
+        let mut signature_bytes = [0u8; 64];
+        conn.read_exact(&mut signature_bytes)
+            .map_err(Error::RemoteSignerConnect)?;
+        let signature = tendermint::Signature::try_from(
+            signature_bytes.as_slice(),
+        )
+        .map_err(|err| {
+            Error::RemoteSignerHandshakeFailed(err.to_string())
+        })?;
+        signer
+            .identity_pubkey
+            .verify(&nonce, &signature)
+            .map_err(|err| {
+                Error::RemoteSignerHandshakeFailed(err.to_string())
+            })?;
+
+        let request_timeout = Some(signer.request_timeout.into());
+        let set_timeouts = match &conn {
+            RemoteSignerConn::Tcp(s) => s
+                .set_read_timeout(request_timeout)
+                .and_then(|()| s.set_write_timeout(request_timeout)),
+            RemoteSignerConn::Unix(s) => s
+                .set_read_timeout(request_timeout)
+                .and_then(|()| s.set_write_timeout(request_timeout)),
+        };
+        set_timeouts.map_err(Error::RemoteSignerConnect)?;
+
+        Ok(Self {
+            conn,
+            last_signed: None,
+        })
+    }
+
+    /// Forward `request` (an opaque, already-encoded vote or proposal sign
+    /// request) to the remote signer and return its signature, refusing to
+    /// send if `state` is at or below the last state successfully signed
+    /// over this channel.
+    pub fn sign(
+        &mut self,
+        state: SignState,
+        request: &[u8],
+    ) -> Result<Vec<u8>> {
+        use std::io::Read;
+
+        if let Some(last) = self.last_signed {
+            if state <= last {
+                return Err(Error::DoubleSignRejected {
+                    height: state.height,
+                    round: state.round,
+                    step: state.step,
+                });
+            }
+        }
+
+        self.conn
+            .write_all(&(request.len() as u32).to_be_bytes())
+            .map_err(Error::RemoteSignerConnect)?;
+        self.conn
+            .write_all(request)
+            .map_err(Error::RemoteSignerConnect)?;
+
+        let mut len_bytes = [0u8; 4];
+        self.conn
+            .read_exact(&mut len_bytes)
+            .map_err(Error::RemoteSignerConnect)?;
+        let mut signature = vec![0u8; u32::from_be_bytes(len_bytes) as usize];
+        self.conn
+            .read_exact(&mut signature)
+            .map_err(Error::RemoteSignerConnect)?;
+
+        self.last_signed = Some(state);
+        Ok(signature)
+    }
+}
+
+/// The active validator signing backend, produced by
+/// [`Config::load_for_node_startup`]. A `tendermint::PrivValidator` adapter
+/// in the node/ledger startup code (outside this config crate, which has
+/// no dependency on the consensus engine's vote/proposal wire types) must
+/// hold on to this and call [`ValidatorSigner::sign`] for every sign
+/// request instead of reading `priv_validator_key.json` off disk directly,
+/// so that configuring `priv_validator_remote` actually moves where votes
+/// get signed rather than just checking that the remote signer is
+/// reachable.
+pub enum ValidatorSigner {
+    Remote(RemoteSignerClient),
+}
+
+impl ValidatorSigner {
+    /// Forward `request` to the backend. See [`RemoteSignerClient::sign`]
+    /// for the double-sign guard this provides for the remote case.
+    pub fn sign(
+        &mut self,
+        state: SignState,
+        request: &[u8],
+    ) -> Result<Vec<u8>> {
+        match self {
+            Self::Remote(client) => client.sign(state, request),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -131,8 +369,6 @@ pub struct IntentGossiper {
     // Simple values
     pub address: Multiaddr,
     pub topics: HashSet<String>,
-    /// The server address to which matchmakers can connect to receive intents
-    pub matchmakers_server_addr: SocketAddr,
 
     // Nested structures ⚠️ no simple values below any of these ⚠️
     pub subscription_filter: SubscriptionFilter,
@@ -146,10 +382,17 @@ pub struct RpcServer {
     pub address: SocketAddr,
 }
 
-#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Matchmaker {
     pub matchmaker_path: Option<PathBuf>,
     pub tx_code_path: Option<PathBuf>,
+    /// The intent gossiper topic this matchmaker subscribes to and matches
+    /// intents from.
+    pub subscribe_topic: Option<String>,
+    /// The address this matchmaker's own server listens on for intents
+    /// dispatched to it, so that each matchmaker can be routed
+    /// independently instead of every matchmaker sharing one transport.
+    pub server_addr: SocketAddr,
 }
 
 impl Ledger {
@@ -194,6 +437,7 @@ impl Ledger {
                     26661,
                 ),
                 instrumentation_namespace: "anoman_tm".to_string(),
+                priv_validator_remote: None,
             },
         }
     }
@@ -212,6 +456,23 @@ impl Ledger {
     pub fn tendermint_dir(&self) -> PathBuf {
         self.shell.tendermint_dir(&self.chain_id)
     }
+
+    /// Get the path to the persisted libp2p node identity keypair.
+    pub fn gossiper_key_path(&self) -> PathBuf {
+        self.chain_dir().join(GOSSIPER_KEY_FILE)
+    }
+}
+
+impl Config {
+    /// Look up the [`Matchmaker`] subscribed to `topic`, if any. Intent
+    /// dispatch should route an incoming intent to this matchmaker's own
+    /// [`Matchmaker::server_addr`] instead of broadcasting it to every
+    /// configured matchmaker.
+    pub fn matchmaker_for_topic(&self, topic: &str) -> Option<&Matchmaker> {
+        self.matchmakers
+            .iter()
+            .find(|m| m.subscribe_topic.as_deref() == Some(topic))
+    }
 }
 
 impl Shell {
@@ -277,10 +538,48 @@ pub enum Error {
          {{protocol}}/{{ip}}/tcp/{{port}}/p2p/{{peerid}}"
     )]
     BadBootstrapPeerFormat(String),
+    #[error("Error while reading interactive input: {0}")]
+    InteractiveInputError(std::io::Error),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("{field} ({addr}) cannot be bound, is it already in use? {source}")]
+    AddressUnavailable {
+        field: String,
+        addr: SocketAddr,
+        source: std::io::Error,
+    },
+    #[error("Could not connect to the remote validator signer: {0}")]
+    RemoteSignerConnect(std::io::Error),
+    #[error(
+        "Remote validator signer did not prove ownership of the configured \
+         identity key: {0}"
+    )]
+    RemoteSignerHandshakeFailed(String),
+    #[error(
+        "Refusing to forward a sign request at height {height}, round \
+         {round}, step {step}: already signed at or past this state"
+    )]
+    DoubleSignRejected { height: u64, round: u64, step: u8 },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Parse a comma-separated list of persistent peer addresses, as entered in
+/// the [`Config::generate_interactive`] wizard.
+fn parse_persistent_peers(
+    input: &str,
+) -> std::result::Result<Vec<TendermintAddress>, String> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            TendermintAddress::from_str(s)
+                .map_err(|err| format!("Invalid peer address {}: {}", s, err))
+        })
+        .collect()
+}
+
 #[derive(Error, Debug)]
 pub enum SerdeError {
     // This is needed for serde https://serde.rs/error-handling.html
@@ -303,7 +602,7 @@ impl Config {
             wasm_dir: DEFAULT_WASM_DIR.into(),
             ledger: Ledger::new(base_dir, chain_id, mode),
             intent_gossiper: IntentGossiper::default(),
-            matchmaker: Matchmaker::default(),
+            matchmakers: vec![],
         }
     }
 
@@ -332,6 +631,68 @@ impl Config {
         }
     }
 
+    /// Like [`Config::load`], but additionally runs the node-startup
+    /// preflight: binding every configured listen address (failing loudly
+    /// on a port conflict instead of an opaque bind error deep in node
+    /// startup) and, for a validator configured with
+    /// [`Tendermint::priv_validator_remote`], connecting to and
+    /// authenticating the remote signer before the node ever needs to sign
+    /// anything. Client and wallet commands must keep using plain
+    /// [`Config::load`]: they only read these addresses to know where to
+    /// connect, and would otherwise fail to bind a port that the
+    /// already-running node holds.
+    ///
+    /// `base_dir` is resolved via [`default_base_dir`] when the `node-id`/
+    /// `ledger run` invocation didn't pass an explicit `--base-dir`
+    /// override, so the node always lands in the same OS-standard data
+    /// directory regardless of the current working directory it's started
+    /// from.
+    ///
+    /// Returns the authenticated [`ValidatorSigner`] alongside the config
+    /// when `priv_validator_remote` is set, rather than discarding the
+    /// connection once the handshake succeeds: node startup must hold on
+    /// to it and use it for every vote/proposal sign request instead of
+    /// reading the consensus key off disk, or configuring a remote signer
+    /// has no actual effect.
+    pub fn load_for_node_startup(
+        base_dir: Option<impl AsRef<Path>>,
+        chain_id: &ChainId,
+        mode: Option<TendermintMode>,
+    ) -> (Self, Option<ValidatorSigner>) {
+        let base_dir = base_dir
+            .map(|dir| dir.as_ref().to_path_buf())
+            .unwrap_or_else(default_base_dir);
+        let config = Self::load(base_dir, chain_id, mode);
+        if let Err(err) = config.validate_addresses() {
+            eprintln!("Invalid config: {}", err);
+            cli::safe_exit(1)
+        }
+        let mut signer = None;
+        if matches!(
+            config.ledger.tendermint.tendermint_mode,
+            TendermintMode::Validator
+        ) {
+            if let Some(remote) =
+                &config.ledger.tendermint.priv_validator_remote
+            {
+                match RemoteSignerClient::connect(remote) {
+                    Ok(client) => {
+                        signer = Some(ValidatorSigner::Remote(client))
+                    }
+                    Err(err) => {
+                        eprintln!(
+                            "Could not reach the configured remote \
+                             validator signer: {}",
+                            err
+                        );
+                        cli::safe_exit(1)
+                    }
+                }
+            }
+        }
+        (config, signer)
+    }
+
     /// Read the config from a file, or generate a default one and write it to
     /// a file if it doesn't already exist. Keys that are expected but not set
     /// in the config file are filled in with default values.
@@ -377,6 +738,111 @@ impl Config {
         Ok(config)
     }
 
+    /// Interactively prompt the operator for the config values that actually
+    /// vary per-deployment (listen addresses, persistent peers, gossiper
+    /// topics, etc.), validating each answer before writing the result.
+    /// Falls back to [`Config::generate`]'s non-interactive defaults when
+    /// stdin is not a TTY.
+    pub fn generate_interactive(
+        base_dir: &Path,
+        chain_id: &ChainId,
+        mode: TendermintMode,
+        replace: bool,
+    ) -> Result<Self> {
+        if !atty::is(atty::Stream::Stdin) {
+            return Self::generate(base_dir, chain_id, mode, replace);
+        }
+
+        let mut config = Config::new(base_dir, chain_id.clone(), mode);
+
+        config.ledger.shell.ledger_address = Input::new()
+            .with_prompt("Ledger listen address")
+            .default(config.ledger.shell.ledger_address)
+            .interact_text()
+            .map_err(Error::InteractiveInputError)?;
+
+        config.ledger.tendermint.rpc_address = Input::new()
+            .with_prompt("Tendermint RPC listen address")
+            .default(config.ledger.tendermint.rpc_address)
+            .interact_text()
+            .map_err(Error::InteractiveInputError)?;
+
+        config.ledger.tendermint.p2p_address = Input::new()
+            .with_prompt("Tendermint P2P listen address")
+            .default(config.ledger.tendermint.p2p_address)
+            .interact_text()
+            .map_err(Error::InteractiveInputError)?;
+
+        let persistent_peers: String = Input::new()
+            .with_prompt(
+                "Persistent peers (comma-separated, e.g. \
+                 id@1.2.3.4:26656), leave empty for none",
+            )
+            .allow_empty(true)
+            .default(String::new())
+            .validate_with(|input: &String| -> std::result::Result<(), String> {
+                parse_persistent_peers(input).map(|_| ())
+            })
+            .interact_text()
+            .map_err(Error::InteractiveInputError)?;
+        config.ledger.tendermint.p2p_persistent_peers =
+            parse_persistent_peers(&persistent_peers)
+                .map_err(Error::InvalidInput)?;
+
+        config.ledger.tendermint.instrumentation_prometheus = Confirm::new()
+            .with_prompt("Enable Prometheus instrumentation?")
+            .default(config.ledger.tendermint.instrumentation_prometheus)
+            .interact()
+            .map_err(Error::InteractiveInputError)?;
+        if config.ledger.tendermint.instrumentation_prometheus {
+            config.ledger.tendermint.instrumentation_prometheus_listen_addr =
+                Input::new()
+                    .with_prompt("Prometheus listen address")
+                    .default(
+                        config
+                            .ledger
+                            .tendermint
+                            .instrumentation_prometheus_listen_addr,
+                    )
+                    .interact_text()
+                    .map_err(Error::InteractiveInputError)?;
+        }
+
+        let topics: String = Input::new()
+            .with_prompt("Intent gossiper topics (comma-separated)")
+            .default(
+                config
+                    .intent_gossiper
+                    .topics
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(","),
+            )
+            .interact_text()
+            .map_err(Error::InteractiveInputError)?;
+        config.intent_gossiper.topics =
+            topics.split(',').map(str::trim).filter(|s| !s.is_empty()).map(String::from).collect();
+
+        let filter_regex: String = Input::new()
+            .with_prompt("Intent gossiper subscription filter regex")
+            .default("asset_v\\d{1,2}".to_string())
+            .validate_with(|input: &String| -> std::result::Result<(), String> {
+                Regex::new(input).map(|_| ()).map_err(|err| err.to_string())
+            })
+            .interact_text()
+            .map_err(Error::InteractiveInputError)?;
+        config.intent_gossiper.subscription_filter =
+            SubscriptionFilter::RegexFilter(
+                Regex::new(&filter_regex).map_err(|err| {
+                    Error::InvalidInput(err.to_string())
+                })?,
+            );
+
+        config.write(base_dir, chain_id, replace)?;
+        Ok(config)
+    }
+
     /// Write configuration to a file.
     pub fn write(
         &self,
@@ -410,6 +876,128 @@ impl Config {
         // Join base dir to the chain ID
         base_dir.as_ref().join(chain_id.to_string()).join(FILENAME)
     }
+
+    /// Pre-flight check that every configured local listen address can
+    /// actually be bound, so a port conflict is reported precisely here
+    /// rather than surfacing as an opaque bind error deep in node startup.
+    /// Also warns when `p2p_addr_book_strict` is set but a configured
+    /// address is not publicly routable.
+    pub fn validate_addresses(&self) -> Result<()> {
+        let mut to_check: Vec<(SocketAddr, String)> = vec![
+            (
+                self.ledger.shell.ledger_address,
+                "ledger.shell.ledger_address".to_string(),
+            ),
+            (
+                self.ledger.tendermint.rpc_address,
+                "ledger.tendermint.rpc_address".to_string(),
+            ),
+            (
+                self.ledger.tendermint.p2p_address,
+                "ledger.tendermint.p2p_address".to_string(),
+            ),
+        ];
+        // Each matchmaker now owns its own server address, so it can be
+        // routed intents independently of the others; check them all.
+        for (ix, matchmaker) in self.matchmakers.iter().enumerate() {
+            to_check.push((
+                matchmaker.server_addr,
+                format!("matchmakers[{}].server_addr", ix),
+            ));
+        }
+        // Only bound to if Prometheus instrumentation is actually enabled;
+        // otherwise this port is never listened on and shouldn't block
+        // startup if it happens to be in use by something else.
+        if self.ledger.tendermint.instrumentation_prometheus {
+            to_check.push((
+                self.ledger.tendermint.instrumentation_prometheus_listen_addr,
+                "ledger.tendermint.instrumentation_prometheus_listen_addr"
+                    .to_string(),
+            ));
+        }
+        for (addr, field) in to_check.iter().cloned() {
+            std::net::TcpListener::bind(addr).map_err(|source| {
+                Error::AddressUnavailable {
+                    field: field.clone(),
+                    addr,
+                    source,
+                }
+            })?;
+            if self.ledger.tendermint.p2p_addr_book_strict
+                && !is_routable(addr.ip())
+            {
+                tracing::warn!(
+                    "{} ({}) is not publicly routable, but \
+                     `p2p_addr_book_strict` is enabled",
+                    field,
+                    addr
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `ip` is plausibly reachable from the public internet, i.e. not a
+/// loopback, unspecified, or private address.
+fn is_routable(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            !ip.is_loopback() && !ip.is_private() && !ip.is_unspecified()
+        }
+        IpAddr::V6(ip) => !ip.is_loopback() && !ip.is_unspecified(),
+    }
+}
+
+/// Generate a new Ed25519 libp2p keypair and persist it at `path`, or load
+/// the existing one if `path` already exists. This gives the node a stable
+/// [`PeerId`] across restarts, so other operators can add it to their
+/// `seed_peers`/persistent-peer entries without booting the whole gossip
+/// stack.
+pub fn generate_or_load_node_identity(
+    path: impl AsRef<Path>,
+) -> std::io::Result<libp2p::identity::Keypair> {
+    let path = path.as_ref();
+    if path.exists() {
+        let mut bytes = std::fs::read(path)?;
+        libp2p::identity::Keypair::ed25519_from_bytes(&mut bytes).map_err(
+            |err| std::io::Error::new(std::io::ErrorKind::InvalidData, err),
+        )
+    } else {
+        let keypair = libp2p::identity::Keypair::generate_ed25519();
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir)?;
+        }
+        if let libp2p::identity::Keypair::Ed25519(ref kp) = keypair {
+            std::fs::write(path, kp.encode())?;
+        }
+        Ok(keypair)
+    }
+}
+
+/// Get this node's derived [`PeerId`], generating and persisting a new
+/// identity keypair under `chain_dir` first if one isn't already stored.
+/// This backs the `node-id` CLI command.
+pub fn node_id(chain_dir: impl AsRef<Path>) -> std::io::Result<PeerId> {
+    let path = chain_dir.as_ref().join(GOSSIPER_KEY_FILE);
+    let keypair = generate_or_load_node_identity(path)?;
+    Ok(PeerId::from(keypair.public()))
+}
+
+/// Execute the `node-id` CLI command: print this node's [`PeerId`] for
+/// `chain_id` under `base_dir`, generating and persisting its identity
+/// keypair first if one isn't already stored. The `cli` crate's `node-id`
+/// subcommand match arm should call this rather than reimplementing the
+/// chain-dir resolution and error handling itself.
+pub fn exec_node_id(base_dir: impl AsRef<Path>, chain_id: &ChainId) {
+    let chain_dir = base_dir.as_ref().join(chain_id.as_str());
+    match node_id(chain_dir) {
+        Ok(peer_id) => println!("{}", peer_id),
+        Err(err) => {
+            eprintln!("Could not determine node ID: {}", err);
+            cli::safe_exit(1)
+        }
+    }
 }
 
 impl Default for IntentGossiper {
@@ -417,10 +1005,6 @@ impl Default for IntentGossiper {
         Self {
             address: Multiaddr::from_str("/ip4/0.0.0.0/tcp/26659").unwrap(),
             topics: vec!["asset_v0"].into_iter().map(String::from).collect(),
-            matchmakers_server_addr: SocketAddr::new(
-                IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
-                26661,
-            ),
             subscription_filter: SubscriptionFilter::RegexFilter(
                 Regex::new("asset_v\\d{1,2}").unwrap(),
             ),
@@ -525,3 +1109,32 @@ And this is correct
        nested:Nested,
     }
 "#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_persistent_peers_filters_empty_entries() {
+        let result = parse_persistent_peers(" , ,").unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn parse_persistent_peers_rejects_invalid_address() {
+        let err = parse_persistent_peers("not-an-address").unwrap_err();
+        assert!(err.contains("not-an-address"));
+    }
+
+    #[test]
+    fn is_routable_rejects_loopback_private_and_unspecified() {
+        assert!(!is_routable(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1))));
+        assert!(!is_routable(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))));
+        assert!(!is_routable(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0))));
+    }
+
+    #[test]
+    fn is_routable_accepts_public_address() {
+        assert!(is_routable(IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8))));
+    }
+}
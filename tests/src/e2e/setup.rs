@@ -1,7 +1,10 @@
+use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::fmt::Display;
 use std::fs::{File, OpenOptions};
 use std::net::SocketAddr;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::process::CommandExt;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str::FromStr;
@@ -22,6 +25,7 @@ use namada::types::chain::ChainId;
 use namada_apps::client::utils;
 use namada_apps::config::genesis::genesis_config::{self, GenesisConfig};
 use namada_apps::{config, wallet};
+use nix::unistd::Pid;
 use rand::Rng;
 use tempfile::{tempdir, TempDir};
 
@@ -44,6 +48,125 @@ const ENV_VAR_KEEP_TEMP: &str = "ANOMA_E2E_KEEP_TEMP";
 pub const ENV_VAR_USE_PREBUILT_BINARIES: &str =
     "ANOMA_E2E_USE_PREBUILT_BINARIES";
 
+/// Env. var pointing to a local build of the tendermint binary
+pub const ENV_VAR_TENDERMINT: &str = "TENDERMINT";
+
+/// Env. var that, when set, rewrites committed snapshot/golden files with
+/// freshly captured output instead of comparing against them. See
+/// [`AnomaCmd::assert_snapshot`].
+pub const ENV_VAR_UPDATE_SNAPSHOTS: &str = "UPDATE_SNAPSHOTS";
+
+/// Env. var that, when set, opts into downloading any genesis WASM missing
+/// from `working_dir/wasm` from [`WASM_RELEASE_BASE_URL`] instead of
+/// requiring a local build. See [`fetch_missing_wasm`].
+pub const ENV_VAR_FETCH_WASM: &str = "ANOMA_E2E_FETCH_WASM";
+
+/// Env. var that, when set, opts into injecting a stack-height limiter
+/// into every genesis WASM on copy. This is off by default: it changes
+/// the WASM's bytes (and therefore gas/step counts are observable by the
+/// tests) and rejects legitimate deep recursion, so it should only be
+/// turned on when specifically exercising the limiter itself or mirroring
+/// production's injected WASM. See [`process_wasm_to_chain_dir`].
+pub const ENV_VAR_INJECT_STACK_HEIGHT_LIMITER: &str =
+    "ANOMA_E2E_INJECT_STACK_HEIGHT_LIMITER";
+
+/// Base URL that missing genesis WASMs are downloaded from, in the same
+/// style as the `RELEASE_PREFIX` used by the join-network flow: the chain
+/// id and file name are appended as `{base}/{chain_id}/{file_name}`.
+pub const WASM_RELEASE_BASE_URL: &str =
+    "https://github.com/anoma/namada/releases/download";
+
+/// Resolved, overridable settings for a single e2e test run. Replaces ad-hoc
+/// `env::var` lookups scattered across the harness with a single object
+/// resolved once from the environment, with a builder for per-test
+/// programmatic overrides (e.g. a test that wants a longer default timeout
+/// or to force debug binaries, without mutating process-global environment
+/// variables).
+#[derive(Debug, Clone)]
+pub struct TestConfig {
+    pub debug: bool,
+    pub keep_temp: bool,
+    pub prebuilt_binaries_dir: Option<PathBuf>,
+    pub tendermint_path: Option<PathBuf>,
+    pub default_timeout: time::Duration,
+    pub log_level: String,
+}
+
+impl Default for TestConfig {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+impl TestConfig {
+    /// Resolve settings from the environment, the same variables previously
+    /// read ad-hoc throughout the harness.
+    pub fn from_env() -> Self {
+        Self {
+            debug: env::var(ENV_VAR_DEBUG)
+                .map(|val| val.to_ascii_lowercase() == "true")
+                .unwrap_or_default(),
+            keep_temp: env::var(ENV_VAR_KEEP_TEMP)
+                .map(|val| val.to_ascii_lowercase() != "false")
+                .unwrap_or_default(),
+            prebuilt_binaries_dir: env::var(ENV_VAR_USE_PREBUILT_BINARIES)
+                .ok()
+                .map(PathBuf::from),
+            tendermint_path: env::var(ENV_VAR_TENDERMINT)
+                .ok()
+                .map(PathBuf::from),
+            default_timeout: time::Duration::from_secs(30),
+            log_level: "info".to_string(),
+        }
+    }
+
+    /// Start building a [`TestConfig`] from the environment, to be
+    /// overridden programmatically before use.
+    pub fn builder() -> TestConfigBuilder {
+        TestConfigBuilder(Self::from_env())
+    }
+}
+
+/// Builder for [`TestConfig`], seeded from the environment.
+#[derive(Debug, Clone)]
+pub struct TestConfigBuilder(TestConfig);
+
+impl TestConfigBuilder {
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.0.debug = debug;
+        self
+    }
+
+    pub fn keep_temp(mut self, keep_temp: bool) -> Self {
+        self.0.keep_temp = keep_temp;
+        self
+    }
+
+    pub fn prebuilt_binaries_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.0.prebuilt_binaries_dir = Some(dir.into());
+        self
+    }
+
+    pub fn tendermint_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.0.tendermint_path = Some(path.into());
+        self
+    }
+
+    pub fn default_timeout(mut self, timeout: time::Duration) -> Self {
+        self.0.default_timeout = timeout;
+        self
+    }
+
+    pub fn log_level(mut self, level: impl Into<String>) -> Self {
+        self.0.log_level = level.into();
+        self
+    }
+
+    pub fn build(self) -> TestConfig {
+        self.0
+    }
+}
+
 /// The E2E tests genesis config source.
 /// This file must contain a single validator with alias "validator-0".
 /// To add more validators, use the [`add_validators`] function in the call to
@@ -99,6 +222,22 @@ pub fn single_node_net() -> Result<Test> {
 pub fn network(
     update_genesis: impl Fn(GenesisConfig) -> GenesisConfig,
     consensus_timeout_commit: Option<&'static str>,
+) -> Result<Test> {
+    network_with_config(
+        update_genesis,
+        consensus_timeout_commit,
+        TestConfig::default(),
+    )
+}
+
+/// Like [`network`], but with an explicit [`TestConfig`] instead of one
+/// resolved from the environment, letting an individual test override e.g.
+/// the default timeout or force debug binaries without mutating
+/// process-global environment variables.
+pub fn network_with_config(
+    update_genesis: impl Fn(GenesisConfig) -> GenesisConfig,
+    consensus_timeout_commit: Option<&'static str>,
+    test_config: TestConfig,
 ) -> Result<Test> {
     INIT.call_once(|| {
         if let Err(err) = color_eyre::install() {
@@ -106,8 +245,8 @@ pub fn network(
         }
     });
 
-    let working_dir = working_dir();
-    let test_dir = TestDir::new();
+    let working_dir = working_dir(&test_config);
+    let test_dir = TestDir::new(&test_config);
 
     // Open the source genesis file
     let genesis = genesis_config::open_genesis_config(
@@ -152,6 +291,7 @@ pub fn network(
         &test_dir,
         "validator",
         format!("{}:{}", std::file!(), std::line!()),
+        &test_config,
     )?;
 
     // Get the generated chain_id` from result of the last command
@@ -181,13 +321,14 @@ pub fn network(
         &chain_dir,
         &net.chain_id,
         genesis.validator.keys(),
-    );
+    )?;
 
     Ok(Test {
         working_dir,
         test_dir,
         net,
         genesis,
+        config: test_config,
     })
 }
 
@@ -208,6 +349,8 @@ pub struct Test {
     pub test_dir: TestDir,
     pub net: Network,
     pub genesis: GenesisConfig,
+    /// Settings resolved for this test run, see [`TestConfig`].
+    pub config: TestConfig,
 }
 
 #[derive(Debug)]
@@ -224,15 +367,10 @@ impl AsRef<Path> for TestDir {
 
 impl TestDir {
     /// Setup a `TestDir` in a temporary directory. The directory will be
-    /// automatically deleted after the test run, unless `ENV_VAR_KEEP_TEMP`
-    /// is set to `true`.
-    pub fn new() -> Self {
-        let keep_temp = match env::var(ENV_VAR_KEEP_TEMP) {
-            Ok(val) => val.to_ascii_lowercase() != "false",
-            _ => false,
-        };
-
-        if keep_temp {
+    /// automatically deleted after the test run, unless `config.keep_temp`
+    /// is set.
+    pub fn new(config: &TestConfig) -> Self {
+        if config.keep_temp {
             let path = tempdir().unwrap().into_path();
             println!(
                 "{}: \"{}\"",
@@ -322,6 +460,18 @@ mod macros {
             $test.run_cmd_as($who, $bin, $args, $timeout_sec, loc)
         }};
     }
+
+    /// Resolve the path to a snapshot fixture next to the calling test's own
+    /// source file, mirroring cargo-test-support's `curr_dir!` pattern. E.g.
+    /// `curr_snapshot!("client-tx-transfer.golden")` from
+    /// `tests/e2e/ledger_tests.rs` resolves to
+    /// `tests/e2e/snapshots/client-tx-transfer.golden`.
+    #[macro_export]
+    macro_rules! curr_snapshot {
+        ($name:expr) => {
+            $crate::e2e::setup::snapshot_path(std::file!(), $name)
+        };
+    }
 }
 
 pub enum Who {
@@ -375,6 +525,8 @@ impl Test {
             Who::NonValidator => "full",
             Who::Validator(_) => "validator",
         };
+        let timeout_sec =
+            timeout_sec.or(Some(self.config.default_timeout.as_secs()));
         run_cmd(
             bin,
             args,
@@ -383,6 +535,79 @@ impl Test {
             &base_dir,
             mode,
             loc,
+            &self.config,
+        )
+    }
+
+    /// Like [`Test::run_cmd_as`], but pins the PTY's terminal window size
+    /// right after the command is spawned.
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_cmd_with_window_size<I, S>(
+        &self,
+        who: Who,
+        bin: Bin,
+        args: I,
+        timeout_sec: Option<u64>,
+        window_size: (u16, u16),
+        loc: String,
+    ) -> Result<AnomaCmd>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let base_dir = self.get_base_dir(&who);
+        let mode = match &who {
+            Who::NonValidator => "full",
+            Who::Validator(_) => "validator",
+        };
+        let timeout_sec =
+            timeout_sec.or(Some(self.config.default_timeout.as_secs()));
+        run_cmd_with_window_size(
+            bin,
+            args,
+            timeout_sec,
+            &self.working_dir,
+            &base_dir,
+            mode,
+            loc,
+            Some(window_size),
+            &self.config,
+        )
+    }
+
+    /// Like [`Test::run_cmd_as`], but applies the given POSIX resource
+    /// limits (`RLIMIT_*`) to the child before it execs.
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_cmd_with_limits<I, S>(
+        &self,
+        who: Who,
+        bin: Bin,
+        args: I,
+        timeout_sec: Option<u64>,
+        limits: &[ResourceLimit],
+        loc: String,
+    ) -> Result<AnomaCmd>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let base_dir = self.get_base_dir(&who);
+        let mode = match &who {
+            Who::NonValidator => "full",
+            Who::Validator(_) => "validator",
+        };
+        let timeout_sec =
+            timeout_sec.or(Some(self.config.default_timeout.as_secs()));
+        run_cmd_with_limits(
+            bin,
+            args,
+            timeout_sec,
+            &self.working_dir,
+            &base_dir,
+            mode,
+            loc,
+            limits,
+            &self.config,
         )
     }
 
@@ -401,11 +626,12 @@ impl Test {
 }
 
 /// A helper that should be ran on start of every e2e test case.
-pub fn working_dir() -> PathBuf {
+pub fn working_dir(config: &TestConfig) -> PathBuf {
     let working_dir = fs::canonicalize("..").unwrap();
 
-    // Check that tendermint is either on $PATH or `TENDERMINT` env var is set
-    if std::env::var("TENDERMINT").is_err() {
+    // Check that tendermint is either on $PATH or a path was resolved from
+    // the `TENDERMINT` env var
+    if config.tendermint_path.is_none() {
         Command::new("which")
             .arg("tendermint")
             .assert()
@@ -452,6 +678,189 @@ impl AnomaBgCmd {
     }
 }
 
+/// Boots and coordinates every validator node of a multi-validator
+/// [`Test`] network, replacing brittle hand-rolled
+/// `run_as!(Validator(i), ...)` calls and fixed sleeps with a few
+/// declarative calls.
+pub struct NetworkController {
+    nodes: Vec<AnomaBgCmd>,
+    /// Each validator's Tendermint RPC address, in validator-index order.
+    rpc_addrs: Vec<SocketAddr>,
+}
+
+impl NetworkController {
+    /// Spawn all `rpc_addrs.len()` validator nodes in `test` concurrently,
+    /// one thread per node, then background them all once every node has
+    /// started; if any node fails to start, that error is returned. `rpc_addrs`
+    /// must list each validator's Tendermint RPC address in validator-index
+    /// order, used by [`NetworkController::wait_for_height`] and
+    /// [`NetworkController::wait_for_all_synced`].
+    pub fn spawn_all(test: &Test, rpc_addrs: Vec<SocketAddr>) -> Result<Self> {
+        let cmds: Vec<Result<AnomaCmd>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..rpc_addrs.len() as u64)
+                .map(|i| {
+                    scope.spawn(move || {
+                        let loc = format!("{}:{}", std::file!(), std::line!());
+                        test.run_cmd_as(
+                            Who::Validator(i),
+                            Bin::Node,
+                            ["ledger"],
+                            Some(40),
+                            loc,
+                        )
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        let nodes = cmds
+            .into_iter()
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(AnomaCmd::background)
+            .collect();
+        Ok(Self { nodes, rpc_addrs })
+    }
+
+    /// Poll every node's RPC endpoint until each reports a block height at
+    /// or above `height`, or return an error naming the last observed
+    /// heights if `timeout` elapses first.
+    pub fn wait_for_height(
+        &self,
+        height: u64,
+        timeout: time::Duration,
+    ) -> Result<()> {
+        let deadline = time::Instant::now() + timeout;
+        loop {
+            let heights = self.observed_heights();
+            if heights.iter().all(|h| matches!(h, Some(h) if *h >= height)) {
+                return Ok(());
+            }
+            if time::Instant::now() >= deadline {
+                return Err(eyre!(
+                    "Timed out after {:?} waiting for all {} nodes to \
+                     reach height {}. Last observed heights: {:?}",
+                    timeout,
+                    self.rpc_addrs.len(),
+                    height,
+                    heights
+                ));
+            }
+            thread::sleep(time::Duration::from_millis(500));
+        }
+    }
+
+    /// Wait until every node reports the same latest block height as every
+    /// other, i.e. the network has caught up to a common height.
+    pub fn wait_for_all_synced(&self, timeout: time::Duration) -> Result<()> {
+        let deadline = time::Instant::now() + timeout;
+        loop {
+            let heights = self.observed_heights();
+            if let Some(first) = heights.first().copied().flatten() {
+                if heights.iter().all(|h| *h == Some(first)) {
+                    return Ok(());
+                }
+            }
+            if time::Instant::now() >= deadline {
+                return Err(eyre!(
+                    "Timed out after {:?} waiting for all {} nodes to sync \
+                     to a common height. Last observed heights: {:?}",
+                    timeout,
+                    self.rpc_addrs.len(),
+                    heights
+                ));
+            }
+            thread::sleep(time::Duration::from_millis(500));
+        }
+    }
+
+    /// Stop and restart the validator at `validator_index`.
+    pub fn restart(
+        &mut self,
+        validator_index: usize,
+        test: &Test,
+    ) -> Result<()> {
+        let old = self.nodes.remove(validator_index);
+        drop(old.foreground());
+        let loc = format!("{}:{}", std::file!(), std::line!());
+        let cmd = test.run_cmd_as(
+            Who::Validator(validator_index as u64),
+            Bin::Node,
+            ["ledger"],
+            Some(40),
+            loc,
+        )?;
+        self.nodes.insert(validator_index, cmd.background());
+        Ok(())
+    }
+
+    fn observed_heights(&self) -> Vec<Option<u64>> {
+        self.rpc_addrs
+            .iter()
+            .map(|addr| query_block_height(addr).ok())
+            .collect()
+    }
+}
+
+/// Query a Tendermint RPC `/status` endpoint for its latest block height.
+fn query_block_height(addr: &SocketAddr) -> Result<u64> {
+    use std::io::{Read, Write};
+
+    let mut stream = std::net::TcpStream::connect(addr)?;
+    stream.set_read_timeout(Some(time::Duration::from_secs(2)))?;
+    write!(
+        stream,
+        "GET /status HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        addr
+    )?;
+    let mut resp = String::new();
+    stream.read_to_string(&mut resp)?;
+    let body = resp
+        .split("\r\n\r\n")
+        .nth(1)
+        .ok_or_else(|| eyre!("Malformed RPC response from {}", addr))?;
+    let json: serde_json::Value = serde_json::from_str(body)?;
+    json["result"]["sync_info"]["latest_block_height"]
+        .as_str()
+        .and_then(|s| s.parse::<u64>().ok())
+        .ok_or_else(|| eyre!("Could not parse block height from {}", addr))
+}
+
+/// Repeatedly query a Tendermint RPC `/status` endpoint and apply
+/// `predicate` to the returned block height, backing off between attempts
+/// starting at 500ms and doubling up to a 1s cap, until `predicate` accepts
+/// or `timeout` elapses. Returns the accepted height, or a timeout error
+/// naming the last observed height (or the last RPC error, if the endpoint
+/// was unreachable).
+pub fn poll_rpc(
+    addr: &SocketAddr,
+    predicate: impl Fn(u64) -> bool,
+    timeout: time::Duration,
+) -> Result<u64> {
+    let deadline = time::Instant::now() + timeout;
+    let mut backoff = time::Duration::from_millis(500);
+    let mut last = Err(eyre!("RPC {} was never queried", addr));
+    loop {
+        last = query_block_height(addr);
+        if let Ok(height) = last {
+            if predicate(height) {
+                return Ok(height);
+            }
+        }
+        if time::Instant::now() >= deadline {
+            return Err(eyre!(
+                "Timed out after {:?} waiting for a matching block height \
+                 from {}. Last observed: {:?}",
+                timeout,
+                addr,
+                last
+            ));
+        }
+        thread::sleep(backoff);
+        backoff = (backoff * 2).min(time::Duration::from_secs(1));
+    }
+}
+
 impl AnomaCmd {
     /// Keep reading the session's output in a background thread to prevent the
     /// buffer from filling up. Call [`AnomaBgCmd::foreground()`] on the
@@ -590,6 +999,226 @@ impl AnomaCmd {
             .send_line(line)
             .map_err(|e| eyre!("Error: {}\nCommand: {}", e, self))
     }
+
+    /// Resize the PTY's terminal window, e.g. to pin an 80x24 or a very
+    /// narrow terminal and assert that the client's formatted output (wrapped
+    /// tables, progress bars, truncation) adapts correctly.
+    pub fn set_window_size(&mut self, rows: u16, cols: u16) -> Result<()> {
+        set_pty_window_size(
+            self.session.get_process().pty().as_raw_fd(),
+            rows,
+            cols,
+        )
+        .map_err(|e| {
+            eyre!(
+                "Error setting window size to {}x{}: {}\nCommand: {}",
+                cols,
+                rows,
+                e,
+                self
+            )
+        })
+    }
+
+    /// Capture all remaining output from this command and compare it,
+    /// after redacting volatile substrings (see [`normalize_snapshot`]),
+    /// against a committed golden file at `snapshot_path` (typically
+    /// resolved with the `curr_snapshot!` macro). With
+    /// `UPDATE_SNAPSHOTS=1` set in the environment, rewrites the golden
+    /// file with the freshly captured output instead of comparing.
+    pub fn assert_snapshot(&mut self, snapshot_path: &Path) -> Result<()> {
+        let output = self.exp_eof()?;
+        let normalized = normalize_snapshot(&output);
+
+        if env::var(ENV_VAR_UPDATE_SNAPSHOTS).is_ok() {
+            if let Some(dir) = snapshot_path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(snapshot_path, &normalized)?;
+            return Ok(());
+        }
+
+        let expected = fs::read_to_string(snapshot_path).map_err(|err| {
+            eyre!(
+                "Could not read snapshot file {}: {}. Run with {}=1 to \
+                 create it.",
+                snapshot_path.to_string_lossy(),
+                err,
+                ENV_VAR_UPDATE_SNAPSHOTS
+            )
+        })?;
+        if expected.trim() != normalized.trim() {
+            return Err(eyre!(
+                "Snapshot mismatch for {}\n\n--- expected ---\n{}\n--- \
+                 actual ---\n{}",
+                snapshot_path.to_string_lossy(),
+                expected,
+                normalized
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Resolve the path to a snapshot fixture relative to a test's own source
+/// file (pass `std::file!()` as `test_src_file`), the same way
+/// cargo-test-support's `curr_dir!` resolves fixtures. Prefer the
+/// `curr_snapshot!` macro over calling this directly.
+pub fn snapshot_path(test_src_file: &str, name: &str) -> PathBuf {
+    Path::new(test_src_file)
+        .parent()
+        .unwrap()
+        .join("snapshots")
+        .join(name)
+}
+
+/// Redact substrings of command output that vary between runs (derived
+/// chain IDs, bech32 addresses, socket addresses, ISO timestamps),
+/// replacing them with stable placeholders so snapshot comparisons aren't
+/// broken by nondeterministic values.
+pub fn normalize_snapshot(input: &str) -> String {
+    let chain_id_re = regex::Regex::new(r"e2e-test\.[0-9a-f]{8,}")
+        .expect("Regex must compile");
+    let socket_addr_re =
+        regex::Regex::new(r"\b(?:\d{1,3}\.){3}\d{1,3}:\d{1,5}\b")
+            .expect("Regex must compile");
+    let bech32_re = regex::Regex::new(r"\b[a-z]+1[ac-hj-np-z02-9]{20,}\b")
+        .expect("Regex must compile");
+    let timestamp_re = regex::Regex::new(
+        r"\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?Z?",
+    )
+    .expect("Regex must compile");
+
+    let out = chain_id_re.replace_all(input, "[CHAIN_ID]");
+    let out = socket_addr_re.replace_all(&out, "[SOCKET_ADDR]");
+    let out = bech32_re.replace_all(&out, "[ADDRESS]");
+    let out = timestamp_re.replace_all(&out, "[TIMESTAMP]");
+    out.into_owned()
+}
+
+/// `TIOCSWINSZ` ioctl argument, matching the kernel's `struct winsize`.
+#[repr(C)]
+struct PtyWinsize {
+    ws_row: libc::c_ushort,
+    ws_col: libc::c_ushort,
+    ws_xpixel: libc::c_ushort,
+    ws_ypixel: libc::c_ushort,
+}
+
+/// Issue the `TIOCSWINSZ` ioctl on a PTY master file descriptor, the same way
+/// a real terminal emulator informs the child of a resize.
+fn set_pty_window_size(
+    fd: std::os::unix::io::RawFd,
+    rows: u16,
+    cols: u16,
+) -> std::io::Result<()> {
+    let winsize = PtyWinsize {
+        ws_row: rows,
+        ws_col: cols,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    let res =
+        unsafe { libc::ioctl(fd, libc::TIOCSWINSZ, &winsize as *const _) };
+    if res != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// A signal to send when tearing down a stuck command, in escalation order.
+#[derive(Debug, Clone, Copy)]
+pub enum Signal {
+    Int,
+    Term,
+    Kill,
+}
+
+impl AnomaCmd {
+    /// Send `signal` to the running process.
+    pub fn kill(&mut self, signal: Signal) -> Result<()> {
+        let sig = match signal {
+            Signal::Int => nix::sys::signal::Signal::SIGINT,
+            Signal::Term => nix::sys::signal::Signal::SIGTERM,
+            Signal::Kill => nix::sys::signal::Signal::SIGKILL,
+        };
+        nix::sys::signal::kill(self.session.pid(), sig).map_err(|e| {
+            eyre!(
+                "Error sending {:?} to command: {}\nCommand: {}",
+                signal,
+                e,
+                self
+            )
+        })
+    }
+
+    /// Non-blocking check for whether the child process has already exited.
+    fn has_exited(&mut self) -> bool {
+        matches!(
+            self.session.status(),
+            Ok(WaitStatus::Exited(..)) | Ok(WaitStatus::Signaled(..))
+        )
+    }
+
+    /// Bounded, escalating teardown: send SIGINT and wait up to `grace`,
+    /// then escalate to SIGTERM, then SIGKILL if the process is still
+    /// alive, each step bounded by `grace`. Unlike a blind Ctrl-C followed
+    /// by a blocking `exp_eof()`, this can never hang the whole test run on
+    /// a node that ignores SIGINT.
+    pub fn terminate_with_timeout(&mut self, grace: time::Duration) {
+        for signal in [Signal::Int, Signal::Term, Signal::Kill] {
+            if self.has_exited() {
+                return;
+            }
+            if self.kill(signal).is_err() {
+                // The process may have already exited between the check
+                // above and the signal.
+                return;
+            }
+            let deadline = time::Instant::now() + grace;
+            while time::Instant::now() < deadline {
+                if self.has_exited() {
+                    return;
+                }
+                thread::sleep(time::Duration::from_millis(100));
+            }
+        }
+    }
+
+    /// Repeatedly evaluate `f` until it returns `Some(_)` or `timeout`
+    /// elapses, backing off between attempts starting at `interval` and
+    /// doubling up to a 1s cap. Returns the first successful value, or a
+    /// timeout error naming the command. Prefer this over a fixed `sleep`
+    /// followed by a single-shot check, which is both racy (the condition
+    /// may not yet hold) and slow (it always waits the full sleep even when
+    /// the condition holds immediately).
+    pub fn wait_until<F, T>(
+        &mut self,
+        mut f: F,
+        timeout: time::Duration,
+        interval: time::Duration,
+    ) -> Result<T>
+    where
+        F: FnMut(&mut Self) -> Option<T>,
+    {
+        let deadline = time::Instant::now() + timeout;
+        let mut backoff = interval;
+        loop {
+            if let Some(value) = f(self) {
+                return Ok(value);
+            }
+            if time::Instant::now() >= deadline {
+                return Err(eyre!(
+                    "Timed out after {:?} waiting for condition on \
+                     command: {}",
+                    timeout,
+                    self
+                ));
+            }
+            thread::sleep(backoff);
+            backoff = (backoff * 2).min(time::Duration::from_secs(1));
+        }
+    }
 }
 
 impl Drop for AnomaCmd {
@@ -597,10 +1226,10 @@ impl Drop for AnomaCmd {
         // attempt to clean up the process
         println!(
             "{}: {}",
-            "> Sending Ctrl+C to command".underline().yellow(),
+            "> Tearing down command".underline().yellow(),
             self.cmd_str,
         );
-        let _result = self.send_control('c');
+        self.terminate_with_timeout(time::Duration::from_secs(5));
         match self.exp_eof() {
             Err(error) => {
                 eprintln!(
@@ -648,6 +1277,145 @@ pub fn run_cmd<I, S>(
     base_dir: impl AsRef<Path>,
     mode: &str,
     loc: String,
+    test_config: &TestConfig,
+) -> Result<AnomaCmd>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    run_cmd_full(
+        bin,
+        args,
+        timeout_sec,
+        working_dir,
+        base_dir,
+        mode,
+        loc,
+        None,
+        &[],
+        test_config,
+    )
+}
+
+/// Like [`run_cmd`], but additionally pins the PTY's terminal window size
+/// before the very first byte of output is read, for tests that exercise
+/// width-dependent CLI rendering. The size is set right after the child is
+/// spawned, so anything the child renders before then uses the PTY's
+/// default size.
+#[allow(clippy::too_many_arguments)]
+pub fn run_cmd_with_window_size<I, S>(
+    bin: Bin,
+    args: I,
+    timeout_sec: Option<u64>,
+    working_dir: impl AsRef<Path>,
+    base_dir: impl AsRef<Path>,
+    mode: &str,
+    loc: String,
+    window_size: Option<(u16, u16)>,
+    test_config: &TestConfig,
+) -> Result<AnomaCmd>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    run_cmd_full(
+        bin,
+        args,
+        timeout_sec,
+        working_dir,
+        base_dir,
+        mode,
+        loc,
+        window_size,
+        &[],
+        test_config,
+    )
+}
+
+/// Like [`run_cmd`], but applies the given POSIX resource limits
+/// (`RLIMIT_*`) to the child before it execs, to deterministically trigger
+/// regression tests for graceful degradation (disk-full during state
+/// writes, fd exhaustion, memory caps) that are otherwise impossible to hit
+/// reliably.
+#[allow(clippy::too_many_arguments)]
+pub fn run_cmd_with_limits<I, S>(
+    bin: Bin,
+    args: I,
+    timeout_sec: Option<u64>,
+    working_dir: impl AsRef<Path>,
+    base_dir: impl AsRef<Path>,
+    mode: &str,
+    loc: String,
+    limits: &[ResourceLimit],
+    test_config: &TestConfig,
+) -> Result<AnomaCmd>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    run_cmd_full(
+        bin,
+        args,
+        timeout_sec,
+        working_dir,
+        base_dir,
+        mode,
+        loc,
+        None,
+        limits,
+        test_config,
+    )
+}
+
+/// A POSIX resource limit (`setrlimit(2)`) applied to a spawned child in a
+/// pre-exec hook before it execs.
+#[derive(Debug, Clone, Copy)]
+pub enum ResourceLimit {
+    /// Maximum size in bytes of a file the process may create
+    /// (`RLIMIT_FSIZE`).
+    FileSize(u64),
+    /// Maximum number of open file descriptors (`RLIMIT_NOFILE`).
+    OpenFiles(u64),
+    /// Maximum address space size in bytes (`RLIMIT_AS`).
+    AddressSpace(u64),
+    /// Maximum CPU time in seconds (`RLIMIT_CPU`).
+    CpuSeconds(u64),
+}
+
+impl ResourceLimit {
+    /// Apply this limit to the current process, as both the soft and hard
+    /// limit. Intended to be called from a `pre_exec` hook, after `fork`
+    /// but before `exec`.
+    fn apply(self) -> nix::Result<()> {
+        use nix::sys::resource::{setrlimit, Resource};
+        let (resource, limit) = match self {
+            ResourceLimit::FileSize(limit) => (Resource::RLIMIT_FSIZE, limit),
+            ResourceLimit::OpenFiles(limit) => {
+                (Resource::RLIMIT_NOFILE, limit)
+            }
+            ResourceLimit::AddressSpace(limit) => {
+                (Resource::RLIMIT_AS, limit)
+            }
+            ResourceLimit::CpuSeconds(limit) => (Resource::RLIMIT_CPU, limit),
+        };
+        setrlimit(resource, limit, limit)
+    }
+}
+
+/// The shared implementation behind [`run_cmd`], [`run_cmd_with_window_size`]
+/// and [`run_cmd_with_limits`].
+#[allow(clippy::too_many_arguments)]
+fn run_cmd_full<I, S>(
+    bin: Bin,
+    args: I,
+    timeout_sec: Option<u64>,
+    working_dir: impl AsRef<Path>,
+    base_dir: impl AsRef<Path>,
+    mode: &str,
+    loc: String,
+    window_size: Option<(u16, u16)>,
+    limits: &[ResourceLimit],
+    test_config: &TestConfig,
 ) -> Result<AnomaCmd>
 where
     I: IntoIterator<Item = S>,
@@ -660,13 +1428,22 @@ where
         Bin::Wallet => "namadaw",
     };
 
+    // `generate_bin_command` resolves debug vs. release and a prebuilt
+    // binaries override from these same env vars; set them from
+    // `test_config` so a programmatic override via [`TestConfigBuilder`]
+    // actually takes effect instead of being silently ignored.
+    env::set_var(ENV_VAR_DEBUG, test_config.debug.to_string());
+    if let Some(dir) = &test_config.prebuilt_binaries_dir {
+        env::set_var(ENV_VAR_USE_PREBUILT_BINARIES, dir);
+    }
+
     let mut run_cmd = generate_bin_command(
         bin_name,
         &working_dir.as_ref().join("Cargo.toml"),
     );
 
     run_cmd
-        .env("ANOMA_LOG", "info")
+        .env("ANOMA_LOG", &test_config.log_level)
         .env("TM_LOG_LEVEL", "info")
         .env("ANOMA_LOG_COLOR", "false")
         .current_dir(working_dir)
@@ -678,6 +1455,20 @@ where
         ])
         .args(args);
 
+    if !limits.is_empty() {
+        let limits = limits.to_vec();
+        unsafe {
+            run_cmd.pre_exec(move || {
+                for limit in &limits {
+                    limit.apply().map_err(|err| {
+                        std::io::Error::from_raw_os_error(err as i32)
+                    })?;
+                }
+                Ok(())
+            });
+        }
+    }
+
     let args: String =
         run_cmd.get_args().map(|s| s.to_string_lossy()).join(" ");
     let cmd_str =
@@ -723,6 +1514,10 @@ where
         log_path,
     };
 
+    if let Some((rows, cols)) = window_size {
+        cmd_process.set_window_size(rows, cols)?;
+    }
+
     println!("{}:\n{}", "> Running".underline().green(), &cmd_process);
 
     if let Bin::Node = &bin {
@@ -758,6 +1553,266 @@ pub fn sleep(seconds: u64) {
     thread::sleep(time::Duration::from_secs(seconds));
 }
 
+/// The result of running a command to completion outside of a PTY, via
+/// [`run_cmd_capture`]. Unlike [`AnomaCmd`], whose `exp_string`/`exp_regex`
+/// only see a single merged PTY stream, stdout and stderr are captured here
+/// separately so tests can assert on exactly the stream a diagnostic was
+/// printed to.
+#[derive(Debug, Clone)]
+pub struct CmdResult {
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    pub status: WaitStatus,
+}
+
+impl CmdResult {
+    pub fn stdout_str(&self) -> String {
+        String::from_utf8_lossy(&self.stdout).into_owned()
+    }
+
+    pub fn stderr_str(&self) -> String {
+        String::from_utf8_lossy(&self.stderr).into_owned()
+    }
+
+    pub fn code_is(&self, code: i32) -> bool {
+        matches!(self.status, WaitStatus::Exited(_, c) if c == code)
+    }
+
+    pub fn stdout_contains(&self, needle: &str) -> bool {
+        self.stdout_str().contains(needle)
+    }
+
+    pub fn stderr_contains(&self, needle: &str) -> bool {
+        self.stderr_str().contains(needle)
+    }
+
+    pub fn stderr_matches(&self, regex: &str) -> Result<bool> {
+        let re = regex::Regex::new(regex)?;
+        Ok(re.is_match(&self.stderr_str()))
+    }
+}
+
+/// Like [`run_cmd`], but spawns the child without a PTY, piping stdout and
+/// stderr separately so the returned [`CmdResult`] lets tests inspect each
+/// stream independently. Useful for asserting on error diagnostics that
+/// would otherwise vanish into the interleaved PTY stream.
+pub fn run_cmd_capture<I, S>(
+    bin: Bin,
+    args: I,
+    working_dir: impl AsRef<Path>,
+    base_dir: impl AsRef<Path>,
+    mode: &str,
+    test_config: &TestConfig,
+) -> Result<CmdResult>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let bin_name = match bin {
+        Bin::Node => "namadan",
+        Bin::Client => "namadac",
+        Bin::Wallet => "namadaw",
+    };
+
+    // See the matching comment in `run_cmd_full`.
+    env::set_var(ENV_VAR_DEBUG, test_config.debug.to_string());
+    if let Some(dir) = &test_config.prebuilt_binaries_dir {
+        env::set_var(ENV_VAR_USE_PREBUILT_BINARIES, dir);
+    }
+
+    let mut cmd = generate_bin_command(
+        bin_name,
+        &working_dir.as_ref().join("Cargo.toml"),
+    );
+    cmd.env("ANOMA_LOG", &test_config.log_level)
+        .env("TM_LOG_LEVEL", "info")
+        .env("ANOMA_LOG_COLOR", "false")
+        .current_dir(working_dir)
+        .args(&[
+            "--base-dir",
+            &base_dir.as_ref().to_string_lossy(),
+            "--mode",
+            mode,
+        ])
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+
+    let child = cmd.spawn()?;
+    let pid = Pid::from_raw(child.id() as i32);
+    let output = child.wait_with_output()?;
+    let code = output.status.code().unwrap_or(-1);
+
+    Ok(CmdResult {
+        stdout: output.stdout,
+        stderr: output.stderr,
+        status: WaitStatus::Exited(pid, code),
+    })
+}
+
+/// A fluent builder around a plain (non-PTY) child process, so tests can
+/// chain `.arg()`/`.env()`/`.current_dir()` and read off an assertion
+/// instead of manually inspecting a [`CmdResult`]. Each assertion failure
+/// prints the command line, call-site location and captured output in the
+/// same format as [`run_cmd`]'s own failure diagnostics.
+pub struct CommandBuilder {
+    command: Command,
+    cmd_str: String,
+    loc: String,
+}
+
+impl CommandBuilder {
+    /// Start building a command to run `program`, recording `loc` (e.g.
+    /// `format!("{}:{}", std::file!(), std::line!())`) for diagnostics.
+    pub fn new(program: impl AsRef<OsStr>, loc: String) -> Self {
+        Self {
+            command: Command::new(&program),
+            cmd_str: program.as_ref().to_string_lossy().into_owned(),
+            loc,
+        }
+    }
+
+    pub fn arg(mut self, arg: impl AsRef<OsStr>) -> Self {
+        self.cmd_str
+            .push_str(&format!(" {}", arg.as_ref().to_string_lossy()));
+        self.command.arg(arg);
+        self
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        for arg in args {
+            self = self.arg(arg);
+        }
+        self
+    }
+
+    pub fn env(
+        mut self,
+        key: impl AsRef<OsStr>,
+        val: impl AsRef<OsStr>,
+    ) -> Self {
+        self.command.env(key, val);
+        self
+    }
+
+    pub fn current_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.command.current_dir(dir);
+        self
+    }
+
+    /// Run the command to completion and capture its output.
+    pub fn run(mut self) -> Result<CmdResult> {
+        self.command
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped());
+        let child = self.command.spawn()?;
+        let pid = Pid::from_raw(child.id() as i32);
+        let output = child.wait_with_output()?;
+        let code = output.status.code().unwrap_or(-1);
+        Ok(CmdResult {
+            stdout: output.stdout,
+            stderr: output.stderr,
+            status: WaitStatus::Exited(pid, code),
+        })
+    }
+
+    /// Like [`Self::run`], but fails instead of blocking indefinitely if
+    /// the command hasn't finished within `timeout`. Supersedes a coarse
+    /// `sleep(u64)` before inspecting the result.
+    ///
+    /// On timeout, the child is killed before returning the error, rather
+    /// than left running detached on the background thread for the rest of
+    /// the test run.
+    pub fn wait_for_output_with_timeout(
+        mut self,
+        timeout: time::Duration,
+    ) -> Result<CmdResult> {
+        let loc = self.loc.clone();
+        let cmd_str = self.cmd_str.clone();
+        self.command
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped());
+        let child = self.command.spawn()?;
+        let pid = Pid::from_raw(child.id() as i32);
+        let (tx, rx) = std::sync::mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(child.wait_with_output());
+        });
+        let output = rx.recv_timeout(timeout).map_err(|_| {
+            let _ = nix::sys::signal::kill(
+                pid,
+                nix::sys::signal::Signal::SIGKILL,
+            );
+            eyre!(
+                "\n\n{}: {}\n{}: {}\n\n{}: timed out after {:?}",
+                "Failed to run".underline().red(),
+                cmd_str,
+                "Location".underline().red(),
+                loc,
+                "Error".underline().red(),
+                timeout
+            )
+        })??;
+        let code = output.status.code().unwrap_or(-1);
+        Ok(CmdResult {
+            stdout: output.stdout,
+            stderr: output.stderr,
+            status: WaitStatus::Exited(pid, code),
+        })
+    }
+
+    fn fail(cmd_str: &str, loc: &str, result: &CmdResult) -> eyre::Error {
+        eyre!(
+            "\n\n{}: {}\n{}: {} \n\n{}: {}\n{}: {}",
+            "Failed to run".underline().red(),
+            cmd_str,
+            "Location".underline().red(),
+            loc,
+            "Stdout".underline().red(),
+            result.stdout_str(),
+            "Stderr".underline().red(),
+            result.stderr_str(),
+        )
+    }
+
+    /// Run the command and assert it exited with code `0`.
+    pub fn assert_success(self) -> Result<CmdResult> {
+        let (cmd_str, loc) = (self.cmd_str.clone(), self.loc.clone());
+        let result = self.run()?;
+        if !result.code_is(0) {
+            return Err(Self::fail(&cmd_str, &loc, &result));
+        }
+        Ok(result)
+    }
+
+    /// Run the command and assert its stdout contains `needle`.
+    pub fn assert_stdout_contains(self, needle: &str) -> Result<CmdResult> {
+        let (cmd_str, loc) = (self.cmd_str.clone(), self.loc.clone());
+        let result = self.run()?;
+        if !result.stdout_contains(needle) {
+            return Err(Self::fail(&cmd_str, &loc, &result));
+        }
+        Ok(result)
+    }
+
+    /// Run the command and assert its stderr matches `regex`.
+    pub fn assert_stderr_matches(self, regex: &str) -> Result<CmdResult> {
+        let (cmd_str, loc) = (self.cmd_str.clone(), self.loc.clone());
+        let result = self.run()?;
+        if !result.stderr_matches(regex)? {
+            return Err(Self::fail(&cmd_str, &loc, &result));
+        }
+        Ok(result)
+    }
+}
+
 #[allow(dead_code)]
 pub mod constants {
     use std::fs;
@@ -800,10 +1855,127 @@ pub mod constants {
     pub const TX_MINT_TOKENS_WASM: &str = "wasm_for_tests/tx_mint_tokens.wasm";
     pub const TX_PROPOSAL_CODE: &str = "wasm_for_tests/tx_proposal_code.wasm";
 
-    /// Find the absolute path to one of the WASM files above
+    /// Find the absolute path to one of the WASM files above, consulting
+    /// the [`super::WasmSourceMap`] configured via
+    /// [`super::ENV_VAR_WASM_MAPDIR`] before falling back to the default
+    /// `../wasm` build output.
     pub fn wasm_abs_path(file_name: &str) -> PathBuf {
         let working_dir = fs::canonicalize("..").unwrap();
-        working_dir.join(file_name)
+        let default_dir = working_dir.join(super::config::DEFAULT_WASM_DIR);
+        super::WasmSourceMap::from_env(default_dir).resolve(file_name)
+    }
+}
+
+/// Env. var holding one or more `subset:source_dir` mappings (comma
+/// separated), consulted before the default `working_dir/wasm` build
+/// output. `subset` is a file name prefix ending in `*`, e.g. `tx_*` or
+/// `vp_*`; earlier entries take precedence over later ones. Modeled on
+/// wasmer's `--mapdir src:dest` flag.
+pub const ENV_VAR_WASM_MAPDIR: &str = "ANOMA_E2E_WASM_MAPDIR";
+
+/// A precedence-ordered set of alternate source directories for genesis
+/// WASM, keyed by file name prefix, so a test run can mix cached,
+/// downloaded and freshly built modules (e.g. pull `tx_*.wasm` from a
+/// released artifacts dir but `vp_*.wasm` from a locally built one).
+#[derive(Debug, Clone)]
+pub struct WasmSourceMap {
+    /// `(prefix, source_dir)` pairs, in precedence order.
+    mappings: Vec<(String, PathBuf)>,
+    default_dir: PathBuf,
+}
+
+impl WasmSourceMap {
+    /// A map with no overrides: every file resolves to `default_dir`.
+    pub fn new(default_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            mappings: Vec::new(),
+            default_dir: default_dir.into(),
+        }
+    }
+
+    /// Parse [`ENV_VAR_WASM_MAPDIR`], if set, on top of `default_dir`.
+    pub fn from_env(default_dir: impl Into<PathBuf>) -> Self {
+        let mut map = Self::new(default_dir);
+        if let Ok(mapdir) = env::var(ENV_VAR_WASM_MAPDIR) {
+            for entry in mapdir.split(',').filter(|s| !s.is_empty()) {
+                let (prefix, dir) =
+                    entry.trim().split_once(':').unwrap_or_else(|| {
+                        panic!(
+                            "Invalid {} entry {:?}, expected \
+                             `subset:source_dir`",
+                            ENV_VAR_WASM_MAPDIR, entry
+                        )
+                    });
+                map = map.with_mapping(prefix, dir);
+            }
+        }
+        map
+    }
+
+    /// Add a mapping, with lower precedence than any already added.
+    pub fn with_mapping(
+        mut self,
+        prefix: impl Into<String>,
+        dir: impl Into<PathBuf>,
+    ) -> Self {
+        self.mappings.push((prefix.into(), dir.into()));
+        self
+    }
+
+    /// Resolve the directory `file_name` should be read from: the first
+    /// mapping whose prefix matches, or [`Self::default_dir`].
+    pub fn source_dir(&self, file_name: &str) -> &Path {
+        self.mappings
+            .iter()
+            .find(|(prefix, _)| {
+                file_name.starts_with(prefix.trim_end_matches('*'))
+            })
+            .map(|(_, dir)| dir.as_path())
+            .unwrap_or(&self.default_dir)
+    }
+
+    /// Resolve the absolute path `file_name` should be read from.
+    pub fn resolve(&self, file_name: &str) -> PathBuf {
+        self.source_dir(file_name).join(file_name)
+    }
+
+    /// List every distinct `.wasm` file name visible across all configured
+    /// mapping directories and [`Self::default_dir`], in precedence order.
+    /// A caller enumerating the genesis WASM set must look here instead of
+    /// just listing `default_dir`, or a file that's only ever been placed
+    /// in a mapped directory (e.g. a release dir pulled in via
+    /// [`ENV_VAR_WASM_MAPDIR`] and never copied into the local build
+    /// output) would never be found.
+    pub fn candidate_file_names(&self) -> Vec<String> {
+        let opts = fs_extra::dir::DirOptions { depth: 1 };
+        let mut seen = HashSet::new();
+        let mut names = Vec::new();
+        let dirs = self
+            .mappings
+            .iter()
+            .map(|(_, dir)| dir.as_path())
+            .chain(std::iter::once(self.default_dir.as_path()));
+        for dir in dirs {
+            let content = match fs_extra::dir::get_dir_content2(dir, &opts) {
+                Ok(content) => content,
+                Err(_) => continue,
+            };
+            for path in content.files.into_iter().map(PathBuf::from) {
+                let is_wasm = matches!(
+                    path.extension().and_then(OsStr::to_str),
+                    Some("wasm")
+                );
+                if !is_wasm {
+                    continue;
+                }
+                let name =
+                    path.file_name().unwrap().to_string_lossy().to_string();
+                if seen.insert(name.clone()) {
+                    names.push(name);
+                }
+            }
+        }
+        names
     }
 }
 
@@ -813,37 +1985,30 @@ pub fn copy_wasm_to_chain_dir<'a>(
     chain_dir: &Path,
     chain_id: &ChainId,
     genesis_validator_keys: impl Iterator<Item = &'a String>,
-) {
+) -> Result<()> {
     // Copy the built WASM files from "wasm" directory in the root of the
     // project.
     let built_wasm_dir = working_dir.join(config::DEFAULT_WASM_DIR);
-    let opts = fs_extra::dir::DirOptions { depth: 1 };
-    let wasm_files: Vec<_> =
-        fs_extra::dir::get_dir_content2(&built_wasm_dir, &opts)
-            .unwrap()
-            .files
-            .into_iter()
-            .map(PathBuf::from)
-            .filter(|path| {
-                matches!(path.extension().and_then(OsStr::to_str), Some("wasm"))
-            })
-            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
-            .collect();
+    if env::var(ENV_VAR_FETCH_WASM).is_ok() {
+        fetch_missing_wasm(&built_wasm_dir, chain_id)?;
+    }
+    let wasm_sources = WasmSourceMap::from_env(built_wasm_dir.clone());
+    // Enumerate candidate file names across every configured mapping
+    // directory, not just `built_wasm_dir`: a file that only exists in a
+    // mapped directory (e.g. pulled from a release dir via
+    // `ENV_VAR_WASM_MAPDIR`) is never copied into `built_wasm_dir` and
+    // would otherwise silently be skipped.
+    let wasm_files = wasm_sources.candidate_file_names();
     if wasm_files.is_empty() {
         panic!(
-            "No WASM files found in {}. Please build or download them them \
-             first.",
-            built_wasm_dir.to_string_lossy()
+            "No WASM files found in {} or any configured {} directory. \
+             Please build or download them first.",
+            built_wasm_dir.to_string_lossy(),
+            ENV_VAR_WASM_MAPDIR
         );
     }
     let target_wasm_dir = chain_dir.join(config::DEFAULT_WASM_DIR);
-    for file in &wasm_files {
-        std::fs::copy(
-            working_dir.join("wasm").join(&file),
-            target_wasm_dir.join(&file),
-        )
-        .unwrap();
-    }
+    process_wasm_to_chain_dir(&wasm_sources, &target_wasm_dir, &wasm_files)?;
 
     // Copy the built WASM files from "wasm" directory to each validator dir
     for validator_name in genesis_validator_keys {
@@ -853,12 +2018,282 @@ pub fn copy_wasm_to_chain_dir<'a>(
             .join(config::DEFAULT_BASE_DIR)
             .join(chain_id.as_str())
             .join(config::DEFAULT_WASM_DIR);
-        for file in &wasm_files {
-            std::fs::copy(
-                working_dir.join("wasm").join(&file),
-                target_wasm_dir.join(&file),
-            )
-            .unwrap();
+        process_wasm_to_chain_dir(
+            &wasm_sources,
+            &target_wasm_dir,
+            &wasm_files,
+        )?;
+    }
+    Ok(())
+}
+
+/// Download any WASM listed in `built_wasm_dir`'s
+/// [`config::DEFAULT_WASM_CHECKSUMS_FILE`] that isn't already present,
+/// from [`WASM_RELEASE_BASE_URL`] keyed by `chain_id`, verifying each
+/// download's sha256 against the expected checksum before caching it in
+/// `built_wasm_dir`. Only called when [`ENV_VAR_FETCH_WASM`] is set, so CI
+/// and joining-validator setups can run without a local WASM build.
+fn fetch_missing_wasm(
+    built_wasm_dir: &Path,
+    chain_id: &ChainId,
+) -> Result<()> {
+    use sha2::{Digest, Sha256};
+
+    let checksums_path =
+        built_wasm_dir.join(config::DEFAULT_WASM_CHECKSUMS_FILE);
+    let expected: std::collections::BTreeMap<String, String> =
+        serde_json::from_reader(File::open(&checksums_path)?)?;
+
+    fs::create_dir_all(built_wasm_dir)?;
+    for (file_name, expected_sha256) in expected {
+        let path = built_wasm_dir.join(&file_name);
+        if path.exists() {
+            continue;
+        }
+        let url = format!(
+            "{}/{}/{}",
+            WASM_RELEASE_BASE_URL,
+            chain_id.as_str(),
+            file_name
+        );
+        let bytes = ureq::get(&url)
+            .call()
+            .map_err(|err| {
+                eyre!("Failed to download missing WASM from {}: {}", url, err)
+            })?
+            .into_reader()
+            .bytes()
+            .collect::<std::io::Result<Vec<u8>>>()?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(&bytes);
+        let actual_sha256 = format!("{:x}", hasher.finalize());
+        if actual_sha256 != expected_sha256 {
+            return Err(eyre!(
+                "Downloaded WASM {} from {} does not match the expected \
+                 checksum (expected {}, got {})",
+                file_name,
+                url,
+                expected_sha256,
+                actual_sha256
+            ));
         }
+        fs::write(&path, &bytes)?;
+    }
+    Ok(())
+}
+
+/// The maximum depth of the synthetic call stack tracked by the injected
+/// stack-height limiter, chosen generously above anything the test WASMs
+/// are expected to recurse to.
+const STACK_HEIGHT_LIMIT: u32 = 65536;
+
+/// The only module name Namada's WASM VM exposes host functions under.
+/// Anything importing from elsewhere did not link against the VM's runtime
+/// and is almost certainly the wrong file.
+const ALLOWED_WASM_IMPORT_MODULE: &str = "env";
+
+/// Verify that `module` only imports host functions from the VM's allowed
+/// namespace, returning a descriptive error naming `path` and the offending
+/// import otherwise.
+fn validate_wasm_imports(
+    module: &parity_wasm::elements::Module,
+    path: &Path,
+) -> Result<()> {
+    if let Some(imports) = module.import_section() {
+        for entry in imports.entries() {
+            if entry.module() != ALLOWED_WASM_IMPORT_MODULE {
+                return Err(eyre!(
+                    "WASM module {} imports `{}.{}` from outside the \
+                     allowed `{}` namespace. Pointed at the wrong file?",
+                    path.display(),
+                    entry.module(),
+                    entry.field(),
+                    ALLOWED_WASM_IMPORT_MODULE
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Deterministically process every file in `file_names`, resolved via
+/// `wasm_sources`, and write the result into `dst_dir`, alongside a
+/// `checksums.json` mapping
+/// each file name to the sha256 of its processed bytes. This guarantees
+/// every validator loads byte-identical WASM regardless of which machine
+/// built it.
+///
+/// Each module is decoded with `parity-wasm`, has its non-semantic custom
+/// sections (debug `name`, `producers`, source maps) stripped so the output
+/// doesn't vary with the compiler's debug metadata, optionally has a
+/// stack-height limiter injected (see [`ENV_VAR_INJECT_STACK_HEIGHT_LIMITER`])
+/// to bound nondeterministic host resource use, and is then re-encoded.
+/// Returns the `(original_path, processed_path)` pair for each file. Fails
+/// with a descriptive error naming the offending file if it cannot be
+/// decoded.
+fn process_wasm_to_chain_dir(
+    wasm_sources: &WasmSourceMap,
+    dst_dir: &Path,
+    file_names: &[String],
+) -> Result<Vec<(PathBuf, PathBuf)>> {
+    use sha2::{Digest, Sha256};
+
+    let inject_stack_height_limiter =
+        env::var(ENV_VAR_INJECT_STACK_HEIGHT_LIMITER).is_ok();
+    let mut checksums = std::collections::BTreeMap::new();
+    let mut paths = Vec::with_capacity(file_names.len());
+    for file_name in file_names {
+        let original_path = wasm_sources.resolve(file_name);
+        let processed_path = dst_dir.join(file_name);
+
+        let mut module = parity_wasm::deserialize_file(&original_path)
+            .map_err(|err| {
+                eyre!(
+                    "Failed to decode WASM module {}: {}. Is this a valid \
+                     wasm file, or did the build point at the wrong file?",
+                    original_path.display(),
+                    err
+                )
+            })?;
+        validate_wasm_imports(&module, &original_path)?;
+        module.sections_mut().retain(|section| {
+            !matches!(
+                section,
+                parity_wasm::elements::Section::Custom(_)
+                    | parity_wasm::elements::Section::Name(_)
+            )
+        });
+        let module = if inject_stack_height_limiter {
+            pwasm_utils::stack_height::inject_limiter(
+                module,
+                STACK_HEIGHT_LIMIT,
+            )
+            .map_err(|_| {
+                eyre!(
+                    "Failed to inject stack-height limiter into {}",
+                    original_path.display()
+                )
+            })?
+        } else {
+            module
+        };
+        let processed_bytes =
+            parity_wasm::serialize(module).map_err(|err| {
+                eyre!(
+                    "Failed to re-encode WASM module {}: {}",
+                    original_path.display(),
+                    err
+                )
+            })?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(&processed_bytes);
+        checksums
+            .insert(file_name.clone(), format!("{:x}", hasher.finalize()));
+
+        std::fs::write(&processed_path, &processed_bytes)?;
+        paths.push((original_path, processed_path));
+    }
+
+    let checksums_path = dst_dir.join(config::DEFAULT_WASM_CHECKSUMS_FILE);
+    let checksums_file = File::create(&checksums_path)?;
+    serde_json::to_writer_pretty(checksums_file, &checksums)?;
+
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_snapshot_redacts_chain_id() {
+        let input = "Chain ID: e2e-test.07a1b2c3d4";
+        assert_eq!(normalize_snapshot(input), "Chain ID: [CHAIN_ID]");
+    }
+
+    #[test]
+    fn normalize_snapshot_redacts_socket_addr() {
+        let input = "Listening on 127.0.0.1:26658";
+        assert_eq!(normalize_snapshot(input), "Listening on [SOCKET_ADDR]");
+    }
+
+    #[test]
+    fn normalize_snapshot_redacts_bech32_address() {
+        let input =
+            "Sent to atest1v4ehgw36g4zyqv2hxwryvdr9v56zvvenxe3zgvc8";
+        assert_eq!(normalize_snapshot(input), "Sent to [ADDRESS]");
+    }
+
+    #[test]
+    fn normalize_snapshot_redacts_timestamp() {
+        let input = "Block time: 2023-04-05T06:07:08.123Z";
+        assert_eq!(normalize_snapshot(input), "Block time: [TIMESTAMP]");
+    }
+
+    #[test]
+    fn normalize_snapshot_leaves_stable_text_alone() {
+        let input = "Transaction applied with result: applied.";
+        assert_eq!(normalize_snapshot(input), input);
+    }
+}
+
+#[cfg(test)]
+mod wasm_source_map_tests {
+    use super::*;
+
+    #[test]
+    fn resolves_to_default_dir_with_no_mappings() {
+        let map = WasmSourceMap::new("/wasm");
+        assert_eq!(
+            map.resolve("tx_transfer.wasm"),
+            PathBuf::from("/wasm/tx_transfer.wasm")
+        );
+    }
+
+    #[test]
+    fn mapping_overrides_default_dir_for_matching_prefix() {
+        let map =
+            WasmSourceMap::new("/wasm").with_mapping("tx_*", "/release");
+        assert_eq!(
+            map.resolve("tx_transfer.wasm"),
+            PathBuf::from("/release/tx_transfer.wasm")
+        );
+        assert_eq!(
+            map.resolve("vp_user.wasm"),
+            PathBuf::from("/wasm/vp_user.wasm")
+        );
+    }
+
+    #[test]
+    fn earlier_mapping_takes_precedence_over_later_one() {
+        let map = WasmSourceMap::new("/wasm")
+            .with_mapping("tx_*", "/first")
+            .with_mapping("tx_*", "/second");
+        assert_eq!(
+            map.source_dir("tx_transfer.wasm"),
+            Path::new("/first")
+        );
+    }
+
+    #[test]
+    fn candidate_file_names_includes_mapped_and_default_dirs() {
+        let default_dir = tempdir().unwrap();
+        let mapped_dir = tempdir().unwrap();
+        fs::write(default_dir.path().join("vp_user.wasm"), b"").unwrap();
+        fs::write(mapped_dir.path().join("tx_transfer.wasm"), b"").unwrap();
+
+        let map = WasmSourceMap::new(default_dir.path())
+            .with_mapping("tx_*", mapped_dir.path());
+        let mut names = map.candidate_file_names();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "tx_transfer.wasm".to_string(),
+                "vp_user.wasm".to_string()
+            ]
+        );
     }
 }